@@ -163,13 +163,181 @@ pub fn multi_message_sending_example() {
     }
 }
 
+//-------------------------------------------------------------------------------------------
+//---------------- worker-pool ---------------------
+//-------------------------------------------------------------------------------------------
+
+// A reusable worker-pool primitive: splits `tasks` into up to `workers`
+// owned chunks up front (rather than indexing the original vector inside each
+// `move` closure, which moves `tasks` into the first thread and leaves
+// nothing to borrow for the rest), spawns one thread per chunk, and
+// funnels every result back through a single cloned `Sender` so the
+// caller can drain them as an iterator. Output order is not preserved.
+pub fn parallel_map<T, R, F>(tasks: Vec<T>, workers: usize, f: F) -> mpsc::Receiver<R>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> R + Send + Sync + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let f = Arc::new(f);
+
+    // split into up to `workers` owned chunks so each thread gets a
+    // vector it fully owns instead of a shared reference into `tasks`.
+    // workers is floored to 1 before the division below, since a
+    // caller-supplied 0 would otherwise divide by zero and panic (and,
+    // for an empty `tasks`, underflow the subtraction first)
+    let workers = workers.max(1);
+    let chunk_size = (tasks.len() + workers - 1) / workers;
+    let mut remaining = tasks;
+    let mut handles = vec![];
+
+    while !remaining.is_empty() {
+        let split_at = chunk_size.min(remaining.len());
+        let chunk: Vec<T> = remaining.drain(..split_at).collect();
+
+        let tx = mpsc::Sender::clone(&tx);
+        let f = Arc::clone(&f);
+
+        let handle = thread::spawn(move || {
+            for task in chunk {
+                tx.send(f(task)).unwrap();
+            }
+        });
+        handles.push(handle);
+    }
+
+    // drop our own tx so the receiver's iterator ends once every
+    // worker's cloned sender has also gone out of scope
+    drop(tx);
+
+    // join the workers on a separate reaper thread rather than here,
+    // so parallel_map can hand rx back to the caller immediately and
+    // results actually stream in as `for result in rx` runs, instead
+    // of the caller only getting rx once every worker has finished.
+    // This thread is never itself joined, so a panicking worker can't
+    // propagate to parallel_map's caller the way handle.join().unwrap()
+    // would inline — at least log it so a dead worker isn't invisible
+    thread::spawn(move || {
+        for handle in handles {
+            if let Err(e) = handle.join() {
+                eprintln!("parallel_map worker panicked: {:?}", e);
+            }
+        }
+    });
+
+    rx
+}
+
+// example usage of parallel_map, mirroring the style of the
+// message-passing examples above
+pub fn parallel_map_example() {
+    let tasks: Vec<u32> = (1..=10).collect();
+
+    let rx = parallel_map(tasks, 4, |n| n * n);
+
+    for result in rx {
+        println!("PARALLEL MAP EXAMPLE: got {}", result);
+    }
+}
+
+// Splits `items` across `workers` threads the same way parallel_map does,
+// has each worker fold its chunk down to a single partial aggregate with
+// the caller-supplied associative `reduce` closure, and sends that one
+// partial over a cloned Sender. The main thread then folds the partials
+// received on rx (treated as an iterator, per message_passing_example_two)
+// into the final value. This is the CPU-bound parallelism counterpart to
+// the I/O-style message-passing demos above: work is actually split and
+// computed concurrently rather than just reported back over a channel.
+pub fn parallel_reduce<T, F>(items: Vec<T>, workers: usize, reduce: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: Fn(T, T) -> T + Send + Sync + 'static,
+{
+    if items.is_empty() {
+        return None;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let reduce = Arc::new(reduce);
+
+    // workers is floored to 1 before the division below, the same
+    // way parallel_map guards against a caller-supplied 0 (which would
+    // otherwise divide by zero and panic)
+    let workers = workers.max(1);
+    let chunk_size = (items.len() + workers - 1) / workers;
+    let mut remaining = items;
+    let mut handles = vec![];
+    let mut chunk_index = 0;
+
+    while !remaining.is_empty() {
+        let split_at = chunk_size.min(remaining.len());
+        let mut chunk: Vec<T> = remaining.drain(..split_at).collect();
+
+        let tx = mpsc::Sender::clone(&tx);
+        let reduce = Arc::clone(&reduce);
+        let index = chunk_index;
+        chunk_index += 1;
+
+        let handle = thread::spawn(move || {
+            // fold this worker's chunk down to a single partial value
+            let mut partial = chunk.remove(0);
+            for item in chunk {
+                partial = reduce(partial, item);
+            }
+            tx.send((index, partial)).unwrap();
+        });
+        handles.push(handle);
+    }
+
+    drop(tx);
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // fold the partials together on the main thread into the final value,
+    // in chunk order rather than arrival order, so an associative but
+    // non-commutative `reduce` still sees the items in their input order
+    let mut received: Vec<(usize, T)> = rx.into_iter().collect();
+    received.sort_by_key(|(index, _)| *index);
+    let mut partials = received.into_iter().map(|(_, partial)| partial);
+    let first = partials.next()?;
+    Some(partials.fold(first, |acc, partial| reduce(acc, partial)))
+}
+
+// example usage of parallel_reduce: summing a vector across 4 workers
+pub fn parallel_reduce_example() {
+    let items: Vec<u64> = (1..=100).collect();
+
+    let sum = parallel_reduce(items, 4, |a, b| a + b);
+
+    println!("PARALLEL REDUCE EXAMPLE: sum is {:?}", sum);
+}
+
 //-------------------------------------------------------------------------------------------
 //---------------- ch16-03-shared-state ---------------------
 //-------------------------------------------------------------------------------------------
 
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
 use std::rc::Rc;
 
+// Most mutex examples in this file call `.lock().unwrap()`, which turns
+// a poisoned lock (one left locked after another thread panicked while
+// holding it) into a cascading panic for everyone else. `lock()` returns
+// `Err(PoisonError)` rather than losing the data, so this helper matches
+// on that result and, on the poisoned branch, calls `into_inner()` to
+// salvage the guarded value and keep going.
+fn lock_or_recover<T>(m: &Mutex<T>) -> MutexGuard<'_, T> {
+    match m.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            println!("mutex was poisoned by a panicked thread, recovering guarded value");
+            poisoned.into_inner()
+        }
+    }
+}
+
 // example of using a mutual exclusive and a lock
 // on the thread
 pub fn mutex_example() {
@@ -211,4 +379,105 @@ pub fn shared_mutex_thread_example() {
     }
 
     println!("Result: {}", *counter.lock().unwrap());
+}
+
+// same counter as shared_mutex_thread_example, but one worker
+// deliberately panics while holding the lock, poisoning it. The
+// remaining workers use lock_or_recover instead of .lock().unwrap()
+// so they salvage the counter and keep going rather than all panicking
+// in turn on the poisoned mutex.
+pub fn shared_mutex_recovery_example() {
+    let counter = Arc::new(Mutex::new(0));
+    let mut handles = vec![];
+
+    for id in 0..10 {
+        let counter = Arc::clone(&counter);
+        let handle = thread::spawn(move || {
+            let mut num = lock_or_recover(&counter);
+
+            if id == 5 {
+                *num += 1;
+                panic!("worker {} panicking while holding the lock", id);
+            }
+
+            *num += 1;
+        });
+        handles.push(handle)
+    }
+
+    for handle in handles {
+        // the panicking worker's handle returns Err here, but the panic
+        // itself is what poisons the mutex for everyone else, so we
+        // simply ignore the join result and move on
+        let _ = handle.join();
+    }
+
+    println!("Result: {}", *lock_or_recover(&counter));
+}
+
+//-------------------------------------------------------------------------------------------
+//---------------- ch16-04-extensible-concurrency-sync-and-send ---------------------
+//-------------------------------------------------------------------------------------------
+
+// example contrasting a non-Send type with its Send fix, mirroring
+// shared_mutex_thread_example above
+pub fn send_bound_example() {
+    // Rc<Mutex<i32>> isn't Send: Rc's reference count isn't updated
+    // atomically, so two threads cloning the same Rc could race and
+    // corrupt the count. thread::spawn requires its closure to be Send,
+    // so this is rejected at compile time rather than at runtime:
+    //
+    // let shared = Rc::new(Mutex::new(0));
+    // let shared = Rc::clone(&shared);
+    // thread::spawn(move || {
+    //     *shared.lock().unwrap() += 1;
+    // });
+    // error[E0277]: `Rc<Mutex<i32>>` cannot be sent between threads safely
+
+    // Arc uses atomic operations for its reference count, so it is Send
+    // (and Sync), and the equivalent code compiles
+    let shared = Arc::new(Mutex::new(0));
+    let shared = Arc::clone(&shared);
+    let handle = thread::spawn(move || {
+        *shared.lock().unwrap() += 1;
+    });
+    handle.join().unwrap();
+}
+
+// Spawns n threads that all invoke the same closure through an Arc.
+// The Send + Sync + 'static bounds are what make this safe: Send lets
+// the Arc<F> move into each spawned thread, Sync lets multiple threads
+// hold a shared reference to the same F through the Arc at once, and
+// 'static guarantees f outlives every thread that might still be
+// running it. Spelling these bounds out on the signature makes the
+// fearless-concurrency guarantees explicit in the API rather than
+// left implicit in a one-off closure.
+pub fn run_on_threads<F: Fn() + Send + Sync + 'static>(f: F, n: usize) {
+    let f = Arc::new(f);
+    let mut handles = vec![];
+
+    for _ in 0..n {
+        let f = Arc::clone(&f);
+        handles.push(thread::spawn(move || f()));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+// example usage of run_on_threads: every thread shares the same counter
+// through the Arc<Mutex<_>> the closure closes over
+pub fn run_on_threads_example() {
+    let counter = Arc::new(Mutex::new(0));
+    let counter_for_closure = Arc::clone(&counter);
+
+    run_on_threads(
+        move || {
+            *counter_for_closure.lock().unwrap() += 1;
+        },
+        10,
+    );
+
+    println!("RUN ON THREADS EXAMPLE: counter is {}", *counter.lock().unwrap());
 }
\ No newline at end of file